@@ -1,189 +1,579 @@
 use crate::{api, utils::semver_split, Data};
 
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::str::FromStr;
 use std::sync::OnceLock;
 
 use eyre::Result;
 use log::trace;
-use regex::Regex;
+use regex::{Captures, Regex};
+use serde::Deserialize;
+
+/// How urgently a detected issue should be surfaced to the user.
+///
+/// Ordered so `Fatal` sorts highest; `find` uses this to show the most
+/// pressing problems first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+	Info,
+	Warning,
+	Error,
+	Fatal,
+}
+
+impl FromStr for Severity {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"info" => Ok(Self::Info),
+			"warning" => Ok(Self::Warning),
+			"error" => Ok(Self::Error),
+			"fatal" => Ok(Self::Fatal),
+			other => Err(format!("unknown severity `{other}`")),
+		}
+	}
+}
+
+/// Rough area of the stack an issue belongs to, used to dedupe `find`'s
+/// results so one log doesn't surface five different phrasings of the
+/// same underlying Java problem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Category {
+	Java,
+	Gpu,
+	Mods,
+	Launcher,
+	Memory,
+}
+
+impl FromStr for Category {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"java" => Ok(Self::Java),
+			"gpu" => Ok(Self::Gpu),
+			"mods" => Ok(Self::Mods),
+			"launcher" => Ok(Self::Launcher),
+			"memory" => Ok(Self::Memory),
+			other => Err(format!("unknown category `{other}`")),
+		}
+	}
+}
+
+/// A single, actionable remedy for an [`AnalyzeResult`].
+///
+/// `label` is short enough to put on a Discord button; `description` is
+/// the step-by-step detail shown once that button is picked.
+#[derive(Debug, Clone)]
+pub struct Solution {
+	pub label: String,
+	pub description: String,
+}
+
+impl Solution {
+	fn new(label: impl Into<String>, description: impl Into<String>) -> Self {
+		Self {
+			label: label.into(),
+			description: description.into(),
+		}
+	}
+}
 
-pub type Issue = Option<(String, String)>;
+/// The outcome of a single [`Analyzer`] recognizing a problem in a log.
+#[derive(Debug, Clone)]
+pub struct AnalyzeResult {
+	pub title: String,
+	pub severity: Severity,
+	pub category: Category,
+	pub solutions: Vec<Solution>,
+}
 
-pub async fn find(log: &str, data: &Data) -> Result<Vec<(String, String)>> {
+/// A single log detector.
+///
+/// Each analyzer looks for one specific signature and, if found, returns a
+/// structured [`AnalyzeResult`] with an ordered list of [`Solution`]s
+/// instead of a single prose blob. Detectors that need network or storage
+/// access (such as [`outdated_launcher`]) are async and stay outside this
+/// trait; they're run separately in [`find`]. Detectors that are just
+/// substring/regex matches against a fixed message live in
+/// [`rules.toml`](rules.toml) instead of as a Rust type; see
+/// [`evaluate_rules`].
+pub trait Analyzer {
+	fn analyze(&self, log: &str) -> Option<AnalyzeResult>;
+}
+
+pub async fn find(log: &str, data: &Data) -> Result<Vec<AnalyzeResult>> {
 	trace!("Checking log for issues");
 
-	let issues = [
-		fabric_internal,
-		flatpak_nvidia,
-		forge_java,
-		intel_hd,
-		java_option,
-		lwjgl_2_java_9,
-		macos_ns,
-		oom,
-		optinotfine,
-		pre_1_12_native_transport_java_9,
-		wrong_java,
-	];
+	let analyzers: [&dyn Analyzer; 4] =
+		[&JavaArchMismatch, &JavaTooOld, &MemoryAllocation, &NativeCrash];
 
-	let mut res: Vec<(String, String)> = issues.iter().filter_map(|issue| issue(log)).collect();
+	let mut res: Vec<AnalyzeResult> = analyzers.iter().filter_map(|a| a.analyze(log)).collect();
+	res.extend(evaluate_rules(log));
 
-	if let Some(issues) = outdated_launcher(log, data).await? {
-		res.push(issues);
+	if let Some(issue) = outdated_launcher(log, data).await? {
+		res.push(issue);
 	}
 
+	res.sort_by_key(|issue| std::cmp::Reverse(issue.severity));
+
+	let mut seen_categories = HashSet::new();
+	res.retain(|issue| seen_categories.insert(issue.category));
+
 	Ok(res)
 }
 
-fn fabric_internal(log: &str) -> Issue {
-	const CLASS_NOT_FOUND: &str = "Caused by: java.lang.ClassNotFoundException: ";
-
-	let issue = (
-		"Fabric Internal Access".to_string(),
-		"The mod you are using is using fabric internals that are not meant \
-        to be used by anything but the loader itself.
-        Those mods break both on Quilt and with fabric updates.
-        If you're using fabric, downgrade your fabric loader could work, \
-        on Quilt you can try updating to the latest beta version, \
-        but there's nothing much to do unless the mod author stops using them."
-			.to_string(),
-	);
-
-	let errors = [
-		&format!("{CLASS_NOT_FOUND}net.fabricmc.fabric.impl"),
-		&format!("{CLASS_NOT_FOUND}net.fabricmc.fabric.mixin"),
-		&format!("{CLASS_NOT_FOUND}net.fabricmc.fabric.loader.impl"),
-		&format!("{CLASS_NOT_FOUND}net.fabricmc.fabric.loader.mixin"),
-		"org.quiltmc.loader.impl.FormattedException: java.lang.NoSuchMethodError:",
-	];
-
-	let found = errors.iter().any(|e| log.contains(e));
-	found.then_some(issue)
+/// Declarative description of a single rule, as read from a rules TOML
+/// file. See [`rules.toml`](rules.toml) for the bundled defaults.
+#[derive(Debug, Deserialize)]
+struct RuleDef {
+	id: String,
+	title: String,
+	severity: String,
+	category: String,
+	#[serde(default)]
+	contains: Vec<String>,
+	#[serde(default)]
+	contains_any: Vec<String>,
+	#[serde(default)]
+	regex: Option<String>,
+	#[serde(default)]
+	suppressed_by: Vec<String>,
+	solution: Vec<SolutionDef>,
 }
 
-fn flatpak_nvidia(log: &str) -> Issue {
-	let issue = (
-		"Outdated Nvidia Flatpak Driver".to_string(),
-		"The Nvidia driver for flatpak is outdated.
-        Please run `flatpak update` to fix this issue. \
-        If that does not solve it, \
-        please wait until the driver is added to Flathub and run it again."
-			.to_string(),
-	);
+#[derive(Debug, Clone, Deserialize)]
+struct SolutionDef {
+	label: String,
+	description: String,
+}
 
-	let found = log.contains("org.lwjgl.LWJGLException: Could not choose GLX13 config")
-		|| log.contains("GLFW error 65545: GLX: Failed to find a suitable GLXFBConfig");
+#[derive(Debug, Deserialize)]
+struct RuleFile {
+	#[serde(default)]
+	rule: Vec<RuleDef>,
+}
 
-	found.then_some(issue)
+#[derive(Clone)]
+struct CompiledRule {
+	id: String,
+	title: String,
+	severity: Severity,
+	category: Category,
+	contains: Vec<String>,
+	contains_any: Vec<String>,
+	regex: Option<Regex>,
+	suppressed_by: Vec<String>,
+	solutions: Vec<SolutionDef>,
 }
 
-fn forge_java(log: &str) -> Issue {
-	let issue = (
-		"Forge Java Bug".to_string(),
-		"Old versions of Forge crash with Java 8u321+.
-            To fix this, update forge to the latest version via the Versions tab
-            (right click on Forge, click Change Version, and choose the latest one)
-            Alternatively, you can download 8u312 or lower. \
-            See [archive](https://github.com/adoptium/temurin8-binaries/releases/tag/jdk8u312-b07)"
-			.to_string(),
-	);
-
-	let found = log.contains("java.lang.NoSuchMethodError: sun.security.util.ManifestEntryVerifier.<init>(Ljava/util/jar/Manifest;)V");
-	found.then_some(issue)
+const DEFAULT_RULES: &str = include_str!("rules.toml");
+
+/// Name of the environment variable used to override the bundled rules
+/// with a file on disk. Unlike the bundled rules, the override file is
+/// re-read and recompiled from disk on every [`find`] call, so editing it
+/// takes effect on the very next log analysis, with no restart needed.
+const RULES_OVERRIDE_ENV: &str = "REFRACTION_ISSUE_RULES_PATH";
+
+fn parse_rules(raw: &str) -> Result<Vec<CompiledRule>, String> {
+	let rule_file: RuleFile = toml::from_str(raw).map_err(|err| err.to_string())?;
+
+	rule_file
+		.rule
+		.into_iter()
+		.map(|def| {
+			let regex = def
+				.regex
+				.as_deref()
+				.map(Regex::new)
+				.transpose()
+				.map_err(|err| err.to_string())?;
+
+			Ok(CompiledRule {
+				regex,
+				severity: def.severity.parse()?,
+				category: def.category.parse()?,
+				id: def.id,
+				title: def.title,
+				contains: def.contains,
+				contains_any: def.contains_any,
+				suppressed_by: def.suppressed_by,
+				solutions: def.solution,
+			})
+		})
+		.collect()
 }
 
-fn intel_hd(log: &str) -> Issue {
-	let issue =
-        (
-        "Intel HD Windows 10".to_string(),
-        "Your drivers don't support windows 10 officially
-        See https://prismlauncher.org/wiki/getting-started/installing-java/#a-note-about-intel-hd-20003000-on-windows-10 for more info".to_string()
-    );
+/// The bundled default rules, compiled once: they're embedded in the
+/// binary at build time, so there's nothing to reload them from.
+fn default_rules() -> &'static [CompiledRule] {
+	static RULES: OnceLock<Vec<CompiledRule>> = OnceLock::new();
+	RULES.get_or_init(|| parse_rules(DEFAULT_RULES).expect("bundled issue rules must be valid"))
+}
 
-	let found = log.contains("org.lwjgl.LWJGLException: Pixel format not accelerated");
-	found.then_some(issue)
+/// The rules to evaluate for this call: the override file from
+/// [`RULES_OVERRIDE_ENV`] if one is configured and parses cleanly,
+/// otherwise the bundled [`default_rules`]. A broken override is logged
+/// and skipped rather than panicking the request that triggered the
+/// reload. Borrows [`default_rules`] instead of cloning it whenever no
+/// override is active, which is the common case on every request.
+fn active_rules() -> Cow<'static, [CompiledRule]> {
+	active_rules_from(std::env::var(RULES_OVERRIDE_ENV).ok().as_deref())
 }
 
-fn java_option(log: &str) -> Issue {
-	static VM_OPTION_REGEX: OnceLock<Regex> = OnceLock::new();
-	static UNRECOGNIZED_OPTION_REGEX: OnceLock<Regex> = OnceLock::new();
+/// Implements [`active_rules`] for a given override path, so the fallback
+/// behaviour is testable without mutating the process environment.
+fn active_rules_from(path: Option<&str>) -> Cow<'static, [CompiledRule]> {
+	let Some(path) = path else {
+		return Cow::Borrowed(default_rules());
+	};
 
-	let vm_option =
-		VM_OPTION_REGEX.get_or_init(|| Regex::new(r"Unrecognized VM option '(.+)'[\r\n]").unwrap());
-	let unrecognized_option = UNRECOGNIZED_OPTION_REGEX
-		.get_or_init(|| Regex::new(r"Unrecognized option: (.+)[\r\n]").unwrap());
+	let raw = match std::fs::read_to_string(path) {
+		Ok(raw) => raw,
+		Err(err) => {
+			log::error!("could not read issue rules override at {path}: {err}; using bundled rules");
+			return Cow::Borrowed(default_rules());
+		}
+	};
 
-	if let Some(captures) = vm_option.captures(log) {
-		let title = if &captures[1] == "UseShenandoahGC" {
-			"Wrong Java Arguments"
-		} else {
-			"Java 8 and below don't support ShenandoahGC"
-		};
-		return Some((
-			title.to_string(),
-			format!("Remove `-XX:{}` from your Java arguments", &captures[1]),
-		));
+	match parse_rules(&raw) {
+		Ok(rules) => Cow::Owned(rules),
+		Err(err) => {
+			log::error!("invalid issue rules override at {path}: {err}; using bundled rules");
+			Cow::Borrowed(default_rules())
+		}
 	}
+}
 
-	if let Some(captures) = unrecognized_option.captures(log) {
-		return Some((
-			"Wrong Java Arguments".to_string(),
-			format!("Remove `{}` from your Java arguments", &captures[1]),
-		));
-	}
+/// Evaluates every rule in [`active_rules`] against `log`, interpolating
+/// regex capture groups (as `{1}`, `{2}`, ...) into matched rules'
+/// solutions, and drops any rule whose match is suppressed by another
+/// rule that also matched.
+fn evaluate_rules(log: &str) -> Vec<AnalyzeResult> {
+	let rules = active_rules();
+
+	let matches: Vec<(&CompiledRule, Option<Captures<'_>>)> = rules
+		.iter()
+		.filter_map(|rule| {
+			if !rule.contains.iter().all(|needle| log.contains(needle.as_str())) {
+				return None;
+			}
+			if !rule.contains_any.is_empty()
+				&& !rule.contains_any.iter().any(|needle| log.contains(needle.as_str()))
+			{
+				return None;
+			}
+
+			match &rule.regex {
+				Some(regex) => regex.captures(log).map(|captures| (rule, Some(captures))),
+				None => Some((rule, None)),
+			}
+		})
+		.collect();
+
+	let matched_ids: HashSet<&str> = matches.iter().map(|(rule, _)| rule.id.as_str()).collect();
+
+	matches
+		.into_iter()
+		.filter(|(rule, _)| {
+			!rule.suppressed_by.iter().any(|id| matched_ids.contains(id.as_str()))
+		})
+		.map(|(rule, captures)| AnalyzeResult {
+			title: rule.title.clone(),
+			severity: rule.severity,
+			category: rule.category,
+			solutions: rule
+				.solutions
+				.iter()
+				.map(|solution| {
+					Solution::new(
+						interpolate(&solution.label, captures.as_ref()),
+						interpolate(&solution.description, captures.as_ref()),
+					)
+				})
+				.collect(),
+		})
+		.collect()
+}
+
+/// Replaces `{1}`, `{2}`, ... placeholders in `template` with the
+/// corresponding regex capture group, if any.
+fn interpolate(template: &str, captures: Option<&Captures>) -> String {
+	let Some(captures) = captures else {
+		return template.to_string();
+	};
 
-	None
+	let mut out = template.to_string();
+	for (i, group) in captures.iter().enumerate().skip(1) {
+		if let Some(group) = group {
+			out = out.replace(&format!("{{{i}}}"), group.as_str());
+		}
+	}
+	out
 }
 
-fn lwjgl_2_java_9(log: &str) -> Issue {
-	let issue = (
-		"Linux: crash with pre-1.13 and Java 9+".to_string(),
-		"Using pre-1.13 (which uses LWJGL 2) with Java 9 or later usually causes a crash. \
-        Switching to Java 8 or below will fix your issue.
-        Alternatively, you can use [Temurin](https://adoptium.net/temurin/releases). \
-        However, multiplayer will not work in versions from 1.8 to 1.11.
-        For more information, type `/tag java`."
-			.to_string(),
-	);
-
-	let found = log.contains("check_match: Assertion `version->filename == NULL || ! _dl_name_match_p (version->filename, map)' failed!");
-	found.then_some(issue)
+struct JavaArchMismatch;
+
+impl Analyzer for JavaArchMismatch {
+	fn analyze(&self, log: &str) -> Option<AnalyzeResult> {
+		static JAVA_ARCH_REGEX: OnceLock<Regex> = OnceLock::new();
+		static NATIVES_REGEX: OnceLock<Regex> = OnceLock::new();
+
+		let java_arch = JAVA_ARCH_REGEX
+			.get_or_init(|| Regex::new(r"using (\d+) \((\w+)\) architecture").unwrap());
+		let natives = NATIVES_REGEX
+			.get_or_init(|| Regex::new(r"natives-(windows|linux|macos)-(\w+)").unwrap());
+
+		let captures = java_arch.captures(log)?;
+		let bits = &captures[1];
+		let java_arch_name = &captures[2];
+
+		if bits == "32" {
+			return Some(AnalyzeResult {
+				title: "32-bit Java".to_string(),
+				severity: Severity::Error,
+				category: Category::Java,
+				solutions: vec![Solution::new(
+					"Install a 64-bit JDK",
+					"Your selected Java is a 32-bit installation, which can't use enough \
+                    memory to run modern Minecraft reliably. Install a 64-bit JDK for \
+                    your platform. See \
+                    https://prismlauncher.org/wiki/getting-started/installing-java/ for \
+                    a guide.",
+				)],
+			});
+		}
+
+		let mismatched_native = natives
+			.captures_iter(log)
+			.map(|c| c[2].to_string())
+			.find(|native_arch| !java_arch_name.eq_ignore_ascii_case(native_arch));
+
+		if let Some(native_arch) = mismatched_native {
+			return Some(AnalyzeResult {
+				title: "Java/Native Architecture Mismatch".to_string(),
+				severity: Severity::Error,
+				category: Category::Java,
+				solutions: vec![Solution::new(
+					"Install a matching JDK",
+					format!(
+						"Your JVM reports the `{java_arch_name}` architecture, but the \
+                        LWJGL natives on the classpath are built for `{native_arch}`. \
+                        Install a 64-bit JDK whose architecture matches your system \
+                        (not just its vendor). See \
+                        https://prismlauncher.org/wiki/getting-started/installing-java/ \
+                        for a guide."
+					),
+				)],
+			});
+		}
+
+		None
+	}
 }
 
-fn macos_ns(log: &str) -> Issue {
-	let issue = (
-    "MacOS NSInternalInconsistencyException".to_string(),
-    "You need to downgrade your Java 8 version. See https://prismlauncher.org/wiki/getting-started/installing-java/#older-minecraft-on-macos".to_string()
-);
+struct JavaTooOld;
+
+impl Analyzer for JavaTooOld {
+	fn analyze(&self, log: &str) -> Option<AnalyzeResult> {
+		static COMPAT_LEVEL_REGEX: OnceLock<Regex> = OnceLock::new();
+		static JAVA_VERSION_REGEX: OnceLock<Regex> = OnceLock::new();
+
+		let compat_level = COMPAT_LEVEL_REGEX
+			.get_or_init(|| Regex::new(r"compatibility level (JAVA_\d+) could not be set").unwrap());
+
+		let captures = compat_level.captures(log)?;
+		let level = &captures[1];
+		let required_major = level.strip_prefix("JAVA_")?;
+
+		let java_version = JAVA_VERSION_REGEX
+			.get_or_init(|| Regex::new(r"Java is version ([\d.]+)").unwrap())
+			.captures(log)
+			.map(|c| c[1].to_string());
+
+		let description = match java_version {
+			Some(version) => format!(
+				"Your instance requires `{level}`, but the selected Java reports \
+                version `{version}`. Install Java {required_major} or newer and switch \
+                to it in the instance's Java settings."
+			),
+			None => format!(
+				"Your instance requires `{level}`. Install Java {required_major} or \
+                newer and switch to it in the instance's Java settings."
+			),
+		};
 
-	let found =
-		log.contains("Terminating app due to uncaught exception 'NSInternalInconsistencyException");
-	found.then_some(issue)
+		Some(AnalyzeResult {
+			title: "Java Version Too Old".to_string(),
+			severity: Severity::Error,
+			category: Category::Java,
+			solutions: vec![Solution::new(
+				format!("Install Java {required_major}+"),
+				description,
+			)],
+		})
+	}
 }
 
-fn oom(log: &str) -> Issue {
-	let issue = (
-		"Out of Memory".to_string(),
-		"Allocating more RAM to your instance could help prevent this crash.".to_string(),
-	);
+struct MemoryAllocation;
+
+impl Analyzer for MemoryAllocation {
+	fn analyze(&self, log: &str) -> Option<AnalyzeResult> {
+		static RAM_REGEX: OnceLock<Regex> = OnceLock::new();
+		static XMX_REGEX: OnceLock<Regex> = OnceLock::new();
+		static XMS_REGEX: OnceLock<Regex> = OnceLock::new();
+
+		let ram = RAM_REGEX.get_or_init(|| Regex::new(r"(\d+) MB RAM").unwrap());
+		let xmx = XMX_REGEX.get_or_init(|| Regex::new(r"-Xmx(\d+)([gGmMkK])").unwrap());
+		let xms = XMS_REGEX.get_or_init(|| Regex::new(r"-Xms(\d+)([gGmMkK])").unwrap());
+
+		let total_ram_mb: u64 = ram.captures(log)?[1].parse().ok()?;
+		let xmx_mb = xmx.captures(log).and_then(|c| parse_size_mb(&c[1], &c[2]));
+		let xms_mb = xms.captures(log).and_then(|c| parse_size_mb(&c[1], &c[2]));
+
+		if xmx_mb.is_none() && xms_mb.is_none() {
+			return None;
+		}
 
-	let found = log.contains("java.lang.OutOfMemoryError") || log.contains("-805306369");
-	found.then_some(issue)
+		let max_recommended_mb = total_ram_mb * 3 / 4;
+
+		if let (Some(xms_mb), Some(xmx_mb)) = (xms_mb, xmx_mb) {
+			if xms_mb > xmx_mb {
+				return Some(AnalyzeResult {
+					title: "Xms Larger Than Xmx".to_string(),
+					severity: Severity::Error,
+					category: Category::Memory,
+					solutions: vec![Solution::new(
+						"Lower -Xms below -Xmx",
+						format!(
+							"Your minimum memory (Xms: {xms_mb} MB) is set higher than your \
+                            maximum memory (Xmx: {xmx_mb} MB). Keep Xms lower than Xmx."
+						),
+					)],
+				});
+			}
+		}
+
+		if let Some(xmx_mb) = xmx_mb {
+			if xmx_mb > max_recommended_mb {
+				return Some(AnalyzeResult {
+					title: "Excessive Memory Allocation".to_string(),
+					severity: Severity::Warning,
+					category: Category::Memory,
+					solutions: vec![Solution::new(
+						"Lower -Xmx",
+						format!(
+							"Your instance is set to allocate {xmx_mb} MB out of {total_ram_mb} \
+                            MB of system RAM, leaving little for the OS and GPU drivers. Set \
+                            Xmx to at most ~75% of your system RAM (around {max_recommended_mb} \
+                            MB here)."
+						),
+					)],
+				});
+			}
+		}
+
+		if let Some(xms_mb) = xms_mb {
+			if xms_mb > max_recommended_mb {
+				return Some(AnalyzeResult {
+					title: "Excessive Minimum Memory Allocation".to_string(),
+					severity: Severity::Warning,
+					category: Category::Memory,
+					solutions: vec![Solution::new(
+						"Lower -Xms",
+						format!(
+							"Your instance reserves {xms_mb} MB of RAM upfront (Xms), which \
+                            is an unusually large minimum. Consider lowering Xms and \
+                            letting the JVM grow its heap up to Xmx as needed."
+						),
+					)],
+				});
+			}
+		}
+
+		None
+	}
 }
 
-fn optinotfine(log: &str) -> Issue {
-	let issue = (
-        "Potential OptiFine Incompatibilities".to_string(),
-        "OptiFine is known to cause problems when paired with other mods. \
-        Try to disable OptiFine and see if the issue persists.
-        Check `/tag optifine` for more info & some typically more compatible alternatives you can use."
-            .to_string(),
-    );
-
-	let found = log.contains("[✔] OptiFine_") || log.contains("[✔] optifabric-");
-	found.then_some(issue)
+fn parse_size_mb(amount: &str, unit: &str) -> Option<u64> {
+	let amount: u64 = amount.parse().ok()?;
+	Some(match unit.to_ascii_lowercase().as_str() {
+		"g" => amount * 1024,
+		"m" => amount,
+		"k" => amount / 1024,
+		_ => return None,
+	})
 }
 
-async fn outdated_launcher(log: &str, data: &Data) -> Result<Issue> {
+struct NativeCrash;
+
+impl Analyzer for NativeCrash {
+	fn analyze(&self, log: &str) -> Option<AnalyzeResult> {
+		static FRAME_REGEX: OnceLock<Regex> = OnceLock::new();
+
+		let frame =
+			FRAME_REGEX.get_or_init(|| Regex::new(r"#\s*C\s+\[([^\]]+)\+0x[0-9a-f]+\]").unwrap());
+
+		if !log.contains("EXCEPTION_ACCESS_VIOLATION (0xc0000005)")
+			&& !log.contains("# Problematic frame:")
+		{
+			return None;
+		}
+
+		let Some(module) = frame.captures(log).map(|c| c[1].to_string()) else {
+			return Some(AnalyzeResult {
+				title: "Native Crash".to_string(),
+				severity: Severity::Error,
+				category: Category::Java,
+				solutions: vec![Solution::new(
+					"Reduce memory & disable native mods",
+					"The game crashed natively (outside the JVM). Try allocating less \
+                    memory to your instance and disabling any mods that use native \
+                    libraries.",
+				)],
+			});
+		};
+
+		static GPU_DRIVER_REGEX: OnceLock<Regex> = OnceLock::new();
+		let gpu_driver = GPU_DRIVER_REGEX
+			.get_or_init(|| Regex::new(r"(?i)^(atig6txx|ig\w*|nvoglv\w*)\.dll$").unwrap());
+
+		if gpu_driver.is_match(&module) {
+			return Some(AnalyzeResult {
+				title: "Native Crash in GPU Driver".to_string(),
+				severity: Severity::Error,
+				category: Category::Gpu,
+				solutions: vec![Solution::new(
+					"Update or roll back your GPU driver",
+					format!(
+						"The game crashed inside `{module}`, which is part of your GPU \
+                        driver. Update your GPU driver, or roll it back to a previous \
+                        version if the crash started after an update."
+					),
+				)],
+			});
+		}
+
+		Some(AnalyzeResult {
+			title: "Native Crash".to_string(),
+			severity: Severity::Error,
+			category: Category::Mods,
+			solutions: vec![Solution::new(
+				"Disable native mods",
+				format!(
+					"The game crashed inside `{module}`, which is a native library. A \
+                    native mod likely crashed; try disabling multithreading/rendering \
+                    mods."
+				),
+			)],
+		})
+	}
+}
+
+async fn outdated_launcher(log: &str, data: &Data) -> Result<Option<AnalyzeResult>> {
 	static OUTDATED_LAUNCHER_REGEX: OnceLock<Regex> = OnceLock::new();
 	let outdated_launcher = OUTDATED_LAUNCHER_REGEX.get_or_init(|| {
 		Regex::new("Prism Launcher version: ((?:([0-9]+)\\.)?([0-9]+)\\.([0-9]+))").unwrap()
@@ -216,58 +606,244 @@ async fn outdated_launcher(log: &str, data: &Data) -> Result<Issue> {
 		|| (log_version_parts[0] == latest_version_parts[0]
 			&& log_version_parts[1] < latest_version_parts[1])
 	{
-		let issue = (
-        	"Outdated Prism Launcher".to_string(),
-        	format!("Your installed version is {log_version}, while the newest version is {latest_version}.\nPlease update; for more info see https://prismlauncher.org/download/")
-        );
-
-		Ok(Some(issue))
+		Ok(Some(AnalyzeResult {
+			title: "Outdated Prism Launcher".to_string(),
+			severity: Severity::Info,
+			category: Category::Launcher,
+			solutions: vec![Solution::new(
+				"Update Prism Launcher",
+				format!("Your installed version is {log_version}, while the newest version is {latest_version}.\nPlease update; for more info see https://prismlauncher.org/download/"),
+			)],
+		}))
 	} else {
 		Ok(None)
 	}
 }
 
-fn pre_1_12_native_transport_java_9(log: &str) -> Issue {
-	let issue = (
-        "Linux: broken multiplayer with 1.8-1.11 and Java 9+".to_string(),
-        "These versions of Minecraft use an outdated version of Netty which does not properly support Java 9.
+#[cfg(test)]
+mod tests {
+	use super::*;
 
-Switching to Java 8 or below will fix this issue. For more information, type `/tag java`.
+	fn rule_titles(results: &[AnalyzeResult]) -> Vec<&str> {
+		results.iter().map(|issue| issue.title.as_str()).collect()
+	}
 
-If you must use a newer version, do the following:
-- Open `options.txt` (in the main window Edit -> Open .minecraft) and change.
-- Find `useNativeTransport:true` and change it to `useNativeTransport:false`.
-Note: whilst Netty was introduced in 1.7, this option did not exist \
-which is why the issue was not present."
-            .to_string(),
-    );
+	#[test]
+	fn oom_rule_matches() {
+		let results = evaluate_rules("java.lang.OutOfMemoryError: Java heap space");
 
-	let found = log.contains(
-        "java.lang.RuntimeException: Unable to access address of buffer\n\tat io.netty.channel.epoll"
-    );
+		assert_eq!(rule_titles(&results), vec!["Out of Memory"]);
+		assert_eq!(results[0].category, Category::Memory);
+		assert_eq!(results[0].severity, Severity::Fatal);
+	}
 
-	found.then_some(issue)
-}
+	#[test]
+	fn no_rule_matches_unrelated_log() {
+		let results = evaluate_rules("Minecraft exited with code 0");
+
+		assert!(results.is_empty());
+	}
+
+	#[test]
+	fn shenandoah_vm_option_suppresses_the_generic_rule() {
+		let results = evaluate_rules("Unrecognized VM option 'UseShenandoahGC'\n");
+
+		assert_eq!(rule_titles(&results), vec!["Wrong Java Arguments"]);
+		assert_eq!(
+			results[0].solutions[0].description,
+			"Remove `-XX:UseShenandoahGC` from your Java arguments"
+		);
+	}
+
+	#[test]
+	fn other_vm_option_hits_the_generic_rule() {
+		let results = evaluate_rules("Unrecognized VM option 'UseG1GC'\n");
+
+		assert_eq!(
+			rule_titles(&results),
+			vec!["Java 8 and below don't support ShenandoahGC"]
+		);
+		assert_eq!(
+			results[0].solutions[0].description,
+			"Remove `-XX:UseG1GC` from your Java arguments"
+		);
+	}
+
+	#[test]
+	fn forge_java_rule_has_two_ordered_solutions() {
+		let results = evaluate_rules(
+			"java.lang.NoSuchMethodError: sun.security.util.ManifestEntryVerifier.<init>(Ljava/util/jar/Manifest;)V",
+		);
+
+		assert_eq!(rule_titles(&results), vec!["Forge Java Bug"]);
+		assert_eq!(results[0].solutions.len(), 2);
+		assert_eq!(results[0].solutions[0].label, "Update Forge");
+		assert_eq!(results[0].solutions[1].label, "Downgrade Java");
+	}
+
+	#[test]
+	fn wrong_java_switch_interpolates_capture_group() {
+		let results = evaluate_rules(
+			"Please switch to one of the following Java versions for this instance:\nJava version 17.0\n",
+		);
+
+		assert_eq!(rule_titles(&results), vec!["Wrong Java Version"]);
+		assert!(results[0].solutions[0].description.contains("Java version 17.0"));
+	}
+
+	#[test]
+	fn active_rules_falls_back_when_override_is_missing() {
+		let rules = active_rules_from(Some("/nonexistent/rules.toml"));
+
+		assert_eq!(rules.len(), default_rules().len());
+	}
+
+	#[test]
+	fn java_arch_mismatch_flags_32_bit_java() {
+		let log = "Java is version 1.8.0_321, using 32 (x86) architecture";
+
+		let result = JavaArchMismatch.analyze(log).unwrap();
+
+		assert_eq!(result.title, "32-bit Java");
+		assert_eq!(result.category, Category::Java);
+		assert_eq!(result.severity, Severity::Error);
+	}
+
+	#[test]
+	fn java_arch_mismatch_flags_mismatched_natives() {
+		let log = "Java is version 17.0.1, using 64 (amd64) architecture\n\
+            Libraries:\n\
+            Native path: lwjgl-glfw-3.3.2-natives-windows-arm64.jar";
+
+		let result = JavaArchMismatch.analyze(log).unwrap();
+
+		assert_eq!(result.title, "Java/Native Architecture Mismatch");
+		assert!(result.solutions[0].description.contains("amd64"));
+		assert!(result.solutions[0].description.contains("arm64"));
+	}
+
+	#[test]
+	fn java_arch_mismatch_ignores_matching_natives() {
+		let log = "Java is version 17.0.1, using 64 (amd64) architecture\n\
+            Native path: lwjgl-glfw-3.3.2-natives-windows-amd64.jar";
+
+		assert!(JavaArchMismatch.analyze(log).is_none());
+	}
+
+	#[test]
+	fn native_crash_blames_gpu_driver() {
+		let log = "#\n\
+            # A fatal error has been detected by the Java Runtime Environment:\n\
+            #\n\
+            #  EXCEPTION_ACCESS_VIOLATION (0xc0000005) at pc=0x00007ffabcde1234, pid=1234, tid=5678\n\
+            #\n\
+            # Problematic frame:\n\
+            # C  [nvoglv64.dll+0x1a2b3c]\n\
+            #";
+
+		let result = NativeCrash.analyze(log).unwrap();
+
+		assert_eq!(result.title, "Native Crash in GPU Driver");
+		assert_eq!(result.category, Category::Gpu);
+		assert!(result.solutions[0].description.contains("nvoglv64.dll"));
+	}
 
-fn wrong_java(log: &str) -> Issue {
-	static SWITCH_VERSION_REGEX: OnceLock<Regex> = OnceLock::new();
-	let switch_version = SWITCH_VERSION_REGEX.get_or_init(|| Regex::new(
-		r"(?m)Please switch to one of the following Java versions for this instance:[\r\n]+(Java version [\d.]+)",
-).unwrap());
+	#[test]
+	fn native_crash_blames_generic_native_library() {
+		let log = "#\n\
+            # Problematic frame:\n\
+            # C  [libstdc++.so.6+0x1a2b3c]\n\
+            #";
 
-	if let Some(captures) = switch_version.captures(log) {
-		let versions = captures[1].split('\n').collect::<Vec<&str>>().join(", ");
-		return Some((
-            "Wrong Java Version".to_string(),
-            format!("Please switch to one of the following: `{versions}`\nFor more information, type `/tag java`"),
-        ));
+		let result = NativeCrash.analyze(log).unwrap();
+
+		assert_eq!(result.title, "Native Crash");
+		assert_eq!(result.category, Category::Mods);
+		assert!(result.solutions[0].description.contains("libstdc++.so.6"));
 	}
 
-	let issue = (
-        "Java compatibility check skipped".to_string(),
-        "The Java major version may not work with your Minecraft instance. Please switch to a compatible version".to_string()
-    );
+	#[test]
+	fn native_crash_falls_back_without_a_recognizable_frame() {
+		let log = "#\n\
+            #  EXCEPTION_ACCESS_VIOLATION (0xc0000005) at pc=0x00007ffabcde1234, pid=1234, tid=5678\n\
+            #";
+
+		let result = NativeCrash.analyze(log).unwrap();
 
-	log.contains("Java major version is incompatible. Things might break.")
-		.then_some(issue)
+		assert_eq!(result.title, "Native Crash");
+		assert_eq!(result.category, Category::Java);
+	}
+
+	#[test]
+	fn native_crash_ignores_unrelated_logs() {
+		let log = "Everything is fine.";
+
+		assert!(NativeCrash.analyze(log).is_none());
+	}
+
+	#[test]
+	fn java_too_old_reports_detected_version() {
+		let log = "Java is version 1.8.0, using 64 (amd64) architecture\n\
+            Could not create the Java Virtual Machine.\n\
+            Error occurred during initialization of VM\n\
+            java.lang.UnsupportedClassVersionError: compatibility level JAVA_17 could not be set";
+
+		let result = JavaTooOld.analyze(log).unwrap();
+
+		assert_eq!(result.title, "Java Version Too Old");
+		assert_eq!(result.category, Category::Java);
+		assert_eq!(result.severity, Severity::Error);
+		assert!(result.solutions[0].description.contains("JAVA_17"));
+		assert!(result.solutions[0].description.contains("1.8.0"));
+	}
+
+	#[test]
+	fn java_too_old_reports_without_detected_version() {
+		let log = "compatibility level JAVA_17 could not be set";
+
+		let result = JavaTooOld.analyze(log).unwrap();
+
+		assert_eq!(result.title, "Java Version Too Old");
+		assert!(result.solutions[0].description.contains("JAVA_17"));
+		assert!(!result.solutions[0].description.contains("reports"));
+	}
+
+	#[test]
+	fn memory_allocation_flags_xms_larger_than_xmx() {
+		let log = "32000 MB RAM\nJava Arguments: -Xmx8000M -Xms9000M";
+
+		let result = MemoryAllocation.analyze(log).unwrap();
+
+		assert_eq!(result.title, "Xms Larger Than Xmx");
+		assert_eq!(result.severity, Severity::Error);
+	}
+
+	#[test]
+	fn memory_allocation_flags_excessive_xmx() {
+		let log = "16000 MB RAM\nJava Arguments: -Xmx14000M";
+
+		let result = MemoryAllocation.analyze(log).unwrap();
+
+		assert_eq!(result.title, "Excessive Memory Allocation");
+		assert_eq!(result.severity, Severity::Warning);
+	}
+
+	#[test]
+	fn memory_allocation_flags_excessive_xms_with_no_xmx_set() {
+		let log = "16000 MB RAM\nJava Arguments: -Xms14000M";
+
+		let result = MemoryAllocation.analyze(log).unwrap();
+
+		assert_eq!(result.title, "Excessive Minimum Memory Allocation");
+		assert_eq!(result.severity, Severity::Warning);
+	}
+
+	#[test]
+	fn memory_allocation_prefers_xms_larger_than_xmx_over_excessive_xmx() {
+		let log = "16000 MB RAM\nJava Arguments: -Xmx15000M -Xms15500M";
+
+		let result = MemoryAllocation.analyze(log).unwrap();
+
+		assert_eq!(result.title, "Xms Larger Than Xmx");
+	}
 }